@@ -0,0 +1,367 @@
+use regex::Regex;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::ops::Range;
+
+pub struct Config {
+    pub query: String,
+    pub paths: Vec<String>,
+    pub case_sensitive: bool,
+    pub use_regex: bool,
+    pub recursive: bool,
+    pub line_number: bool,
+    pub color: bool,
+}
+
+impl Config {
+    pub fn build(args: impl Iterator<Item = String>) -> Result<Config, &'static str> {
+        let mut positional = Vec::new();
+        let mut use_regex = false;
+        let mut recursive = false;
+        let mut line_number = false;
+        let mut color = false;
+
+        for arg in args.skip(1) {
+            match arg.as_str() {
+                "--regex" | "-E" => use_regex = true,
+                "--recursive" | "-r" => recursive = true,
+                "--line-number" | "-n" => line_number = true,
+                "--color" => color = true,
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+
+        let query = positional.next().ok_or("didn't get a query string")?;
+        let paths: Vec<String> = positional.collect();
+        if paths.is_empty() {
+            return Err("didn't get a file name");
+        }
+
+        let case_sensitive = env::var("MINIGREP_CASE_INSENSITIVE").is_err();
+
+        Ok(Config {
+            query,
+            paths,
+            case_sensitive,
+            use_regex,
+            recursive,
+            line_number,
+            color,
+        })
+    }
+}
+
+/// The matching strategy a search is run with: a plain substring match, or a
+/// compiled regular expression.
+enum Matcher {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn build(config: &Config) -> Result<Matcher, Box<dyn Error>> {
+        if config.use_regex {
+            let pattern = if config.case_sensitive {
+                config.query.clone()
+            } else {
+                format!("(?i){}", config.query)
+            };
+            Ok(Matcher::Regex(Regex::new(&pattern)?))
+        } else if config.case_sensitive {
+            Ok(Matcher::Literal(config.query.clone()))
+        } else {
+            // Case-insensitive literal matching can't be done by lowercasing
+            // `line` and mapping the match back: `to_lowercase()` isn't
+            // byte-length-preserving for every character (e.g. Turkish
+            // `İ`), so the computed span can fall outside `line`'s bounds.
+            // A case-insensitive regex matches directly on `line`'s own
+            // bytes, so the span it returns is always valid for `line`.
+            let pattern = format!("(?i){}", regex::escape(&config.query));
+            Ok(Matcher::Regex(Regex::new(&pattern)?))
+        }
+    }
+
+    /// Returns the byte range of the first match on `line`, if any, so the
+    /// caller can highlight it.
+    fn find(&self, line: &str) -> Option<Range<usize>> {
+        match self {
+            Matcher::Literal(query) => {
+                line.find(query.as_str()).map(|start| start..start + query.len())
+            }
+            Matcher::Regex(re) => re.find(line).map(|m| m.start()..m.end()),
+        }
+    }
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let matcher = Matcher::build(&config)?;
+
+    let mut files = Vec::new();
+    for path in &config.paths {
+        files.extend(collect_files(path, config.recursive)?);
+    }
+
+    let show_path = files.len() > 1;
+    let use_color = config.color && io::stdout().is_terminal();
+
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+
+    for file in &files {
+        let contents = fs::read_to_string(file)?;
+
+        for (index, line) in contents.lines().enumerate() {
+            if let Some(match_span) = matcher.find(line) {
+                let path = if show_path { Some(file.as_str()) } else { None };
+                print_result(
+                    &mut writer,
+                    path,
+                    index + 1,
+                    line,
+                    match_span,
+                    config.line_number,
+                    use_color,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a single matched line, optionally prefixed with its file path and
+/// 1-based line number, and with the matched span highlighted in color.
+fn print_result(
+    writer: &mut impl Write,
+    path: Option<&str>,
+    line_no: usize,
+    line: &str,
+    match_span: Range<usize>,
+    show_line_number: bool,
+    use_color: bool,
+) -> io::Result<()> {
+    if let Some(path) = path {
+        write!(writer, "{}: ", path)?;
+    }
+
+    if show_line_number {
+        write!(writer, "{}: ", line_no)?;
+    }
+
+    if use_color {
+        writeln!(
+            writer,
+            "{}\x1b[1;31m{}\x1b[0m{}",
+            &line[..match_span.start],
+            &line[match_span.start..match_span.end],
+            &line[match_span.end..]
+        )
+    } else {
+        writeln!(writer, "{}", line)
+    }
+}
+
+/// Resolves a single CLI path into the list of regular files it names,
+/// descending into directories depth-first when `recursive` is set.
+fn collect_files(path: &str, recursive: bool) -> Result<Vec<String>, Box<dyn Error>> {
+    let metadata = fs::metadata(path)?;
+
+    if metadata.is_file() {
+        return Ok(vec![path.to_string()]);
+    }
+
+    if !recursive {
+        return Err(format!("{}: is a directory (use --recursive to search it)", path).into());
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let entry_path = entry?.path();
+        let entry_path = entry_path.to_string_lossy().into_owned();
+        files.extend(collect_files(&entry_path, recursive)?);
+    }
+
+    Ok(files)
+}
+
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| line.contains(query))
+        .collect()
+}
+
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let query = query.to_lowercase();
+
+    contents
+        .lines()
+        .filter(|line| line.to_lowercase().contains(&query))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_sensitive() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.";
+
+        assert_eq!(vec!["safe, fast, productive."], search(query, contents));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let query = "rUsT";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+        assert_eq!(
+            vec!["Rust:", "Trust me."],
+            search_case_insensitive(query, contents)
+        );
+    }
+
+    fn test_config(query: &str, use_regex: bool, case_sensitive: bool) -> Config {
+        Config {
+            query: query.to_string(),
+            paths: Vec::new(),
+            case_sensitive,
+            use_regex,
+            recursive: false,
+            line_number: false,
+            color: false,
+        }
+    }
+
+    #[test]
+    fn regex_mode_matches_a_pattern() {
+        let config = test_config("r.st", true, true);
+        let matcher = Matcher::build(&config).unwrap();
+
+        assert_eq!(Some(0..4), matcher.find("rust is fun"));
+    }
+
+    #[test]
+    fn regex_mode_honors_case_insensitivity() {
+        let config = test_config("rust", true, false);
+        let matcher = Matcher::build(&config).unwrap();
+
+        assert_eq!(Some(0..4), matcher.find("RUST is fun"));
+    }
+
+    #[test]
+    fn invalid_regex_is_reported_as_an_error() {
+        let config = test_config("[unterminated", true, true);
+
+        assert!(Matcher::build(&config).is_err());
+    }
+
+    #[test]
+    fn collect_files_errors_on_directory_without_recursive() {
+        let dir =
+            std::env::temp_dir().join(format!("minigrep_test_no_recursive_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let result = collect_files(dir.to_str().unwrap(), false);
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn collect_files_walks_directories_recursively() {
+        let dir =
+            std::env::temp_dir().join(format!("minigrep_test_recursive_{}", std::process::id()));
+        let nested = dir.join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.join("a.txt"), "hello").unwrap();
+        fs::write(nested.join("b.txt"), "world").unwrap();
+
+        let mut files = collect_files(dir.to_str().unwrap(), true).unwrap();
+        files.sort();
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().any(|f| f.ends_with("a.txt")));
+        assert!(files.iter().any(|f| f.ends_with("b.txt")));
+    }
+
+    #[test]
+    fn print_result_plain() {
+        let mut buffer = Vec::new();
+        print_result(&mut buffer, None, 2, "safe, fast, productive.", 5..9, false, false).unwrap();
+
+        assert_eq!(
+            "safe, fast, productive.\n",
+            String::from_utf8(buffer).unwrap()
+        );
+    }
+
+    #[test]
+    fn print_result_with_line_number_and_path() {
+        let mut buffer = Vec::new();
+        print_result(
+            &mut buffer,
+            Some("poem.txt"),
+            2,
+            "safe, fast, productive.",
+            5..9,
+            true,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            "poem.txt: 2: safe, fast, productive.\n",
+            String::from_utf8(buffer).unwrap()
+        );
+    }
+
+    #[test]
+    fn print_result_with_color() {
+        let mut buffer = Vec::new();
+        print_result(&mut buffer, None, 1, "safe, fast, productive.", 6..10, false, true).unwrap();
+
+        assert_eq!(
+            "safe, \u{1b}[1;31mfast\u{1b}[0m, productive.\n",
+            String::from_utf8(buffer).unwrap()
+        );
+    }
+
+    #[test]
+    fn literal_case_insensitive_match_survives_multibyte_case_folding() {
+        // `İ` (U+0130) lowercases to a 3-byte `i̇`, one byte longer than its
+        // own 2-byte UTF-8 encoding, so a naive lowercase-then-slice
+        // approach computes a match span that runs past the end of the
+        // (shorter) original line.
+        let config = test_config("bul", false, false);
+        let matcher = Matcher::build(&config).unwrap();
+        let line = "İstanbul";
+
+        let match_span = matcher.find(line).expect("should match despite the unicode prefix");
+
+        let mut buffer = Vec::new();
+        print_result(&mut buffer, None, 1, line, match_span, false, true).unwrap();
+
+        assert_eq!(
+            "İstan\u{1b}[1;31mbul\u{1b}[0m\n",
+            String::from_utf8(buffer).unwrap()
+        );
+    }
+}
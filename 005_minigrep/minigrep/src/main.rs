@@ -2,12 +2,11 @@ use minigrep::Config;
 use std::env;
 use std::process;
 fn main() {
-    let args: Vec<String> = env::args().collect(); // the first value in the args is the name of the binary
-    let config = Config::new(&args).unwrap_or_else(|err| {
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
         eprintln!("Problem parsing arguments: {}", err);
         process::exit(1)
     });
-    println!("Searching for {} in file {}", config.query, config.filename);
+    println!("Searching for {} in {}", config.query, config.paths.join(", "));
     if let Err(e) = minigrep::run(config) {
         eprintln!("Application Error: {}", e);
         process::exit(1);